@@ -1,4 +1,97 @@
-use std::{collections::HashMap, fmt, fs};
+use std::{
+    collections::{HashMap, HashSet},
+    convert::TryFrom,
+    fmt, fs,
+    io::IsTerminal,
+};
+
+use clap::{ArgGroup, Parser, ValueEnum};
+
+/// Errors produced while parsing a query pattern string into [`QueryPatternState`] rows.
+#[derive(Debug)]
+enum ParseError {
+    /// A character in the pattern didn't map to `G`/`Y`/`X`/`?`/`*`.
+    InvalidPatternChar { ch: char, line: usize, col: usize },
+    /// A pattern row didn't have the same number of characters as the solution.
+    LengthMismatch {
+        expected: usize,
+        got: usize,
+        line: usize,
+    },
+    /// A boolean query expression contained a token that wasn't expected at that position.
+    UnexpectedToken { found: String, pos: usize },
+    /// A boolean query expression had an opening or closing parenthesis without a match.
+    UnmatchedParen { pos: usize },
+    /// A boolean query expression parsed successfully but left trailing tokens unconsumed, e.g. two
+    /// pattern rows with no `&`/`|` between them.
+    TrailingInput { pos: usize },
+    /// A boolean query expression was empty.
+    EmptyExpression,
+    /// A `--guess` entry wasn't in `WORD=PATTERN` form, or its pattern half didn't parse.
+    InvalidGuessFeedback { raw: String },
+    /// A `${name}` placeholder had an opening brace with no matching closing brace.
+    UnterminatedPlaceholder { line: usize, col: usize },
+    /// Two `--guess` entries had guesses of different lengths.
+    MixedGuessLengths {
+        expected: usize,
+        got: usize,
+        guess: String,
+    },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseError::InvalidPatternChar { ch, line, col } => write!(
+                f,
+                "invalid pattern character '{}' at line {}, column {}",
+                ch, line, col
+            ),
+            ParseError::LengthMismatch {
+                expected,
+                got,
+                line,
+            } => write!(
+                f,
+                "pattern on line {} has length {}, expected {}",
+                line, got, expected
+            ),
+            ParseError::UnexpectedToken { found, pos } => {
+                write!(f, "unexpected token '{}' at position {}", found, pos)
+            }
+            ParseError::UnmatchedParen { pos } => {
+                write!(f, "unmatched parenthesis at position {}", pos)
+            }
+            ParseError::TrailingInput { pos } => write!(
+                f,
+                "unexpected trailing input at position {} (missing '&' or '|'?)",
+                pos
+            ),
+            ParseError::EmptyExpression => write!(f, "query expression is empty"),
+            ParseError::InvalidGuessFeedback { raw } => write!(
+                f,
+                "invalid --guess entry '{}', expected WORD=PATTERN (e.g. crane=GYXXY)",
+                raw
+            ),
+            ParseError::UnterminatedPlaceholder { line, col } => write!(
+                f,
+                "unterminated '${{' placeholder at line {}, column {}",
+                line, col
+            ),
+            ParseError::MixedGuessLengths {
+                expected,
+                got,
+                guess,
+            } => write!(
+                f,
+                "--guess '{}' has length {}, but earlier guesses have length {}; all --guess entries must be the same length",
+                guess, got, expected
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
 
 // valid pattern states
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -8,13 +101,14 @@ enum PatternState {
     Grey,   // incorrect letter
 }
 
-impl From<char> for PatternState {
-    fn from(c: char) -> Self {
+impl TryFrom<char> for PatternState {
+    type Error = char;
+    fn try_from(c: char) -> Result<Self, Self::Error> {
         match c.to_ascii_uppercase() {
-            'G' => PatternState::Green,
-            'Y' => PatternState::Yellow,
-            'X' => PatternState::Grey,
-            _ => panic!("Invalid pattern character: '{}'", c),
+            'G' => Ok(PatternState::Green),
+            'Y' => Ok(PatternState::Yellow),
+            'X' => Ok(PatternState::Grey),
+            other => Err(other),
         }
     }
 }
@@ -30,19 +124,24 @@ impl fmt::Display for PatternState {
 }
 
 // valid pattern states, with extra filters for queries
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 enum QueryPatternState {
     Base(PatternState),
     AnyValid,
     Any,
+    /// A named placeholder (written `$x` for a one-character name, or `${name}` for a longer one, in pattern
+    /// text). All positions sharing the same name, whether
+    /// within one row or across multiple rows of a board, must resolve to the same concrete `PatternState`.
+    Named(String),
 }
 
-impl From<char> for QueryPatternState {
-    fn from(c: char) -> Self {
+impl TryFrom<char> for QueryPatternState {
+    type Error = char;
+    fn try_from(c: char) -> Result<Self, Self::Error> {
         match c.to_ascii_uppercase() {
-            '?' => QueryPatternState::AnyValid,
-            '*' => QueryPatternState::Any,
-            other => QueryPatternState::Base(PatternState::from(other)),
+            '?' => Ok(QueryPatternState::AnyValid),
+            '*' => Ok(QueryPatternState::Any),
+            other => PatternState::try_from(other).map(QueryPatternState::Base),
         }
     }
 }
@@ -53,25 +152,95 @@ impl fmt::Display for QueryPatternState {
             QueryPatternState::Base(state) => write!(f, "{}", state),
             QueryPatternState::AnyValid => write!(f, "?"),
             QueryPatternState::Any => write!(f, "*"),
+            QueryPatternState::Named(name) if name.chars().count() == 1 => write!(f, "${}", name),
+            QueryPatternState::Named(name) => write!(f, "${{{}}}", name),
         }
     }
 }
 
 impl QueryPatternState {
+    /// The states this position could resolve to, ignoring any cross-position tying. For a `Named`
+    /// placeholder this is the same pool as `Any`; the tying itself is handled by the expansion functions.
     fn possible_states(&self) -> Vec<PatternState> {
         match self {
             QueryPatternState::Base(state) => vec![*state],
             QueryPatternState::AnyValid => vec![PatternState::Green, PatternState::Yellow],
-            QueryPatternState::Any => vec![PatternState::Green, PatternState::Yellow, PatternState::Grey],
+            QueryPatternState::Any | QueryPatternState::Named(_) => {
+                vec![PatternState::Green, PatternState::Yellow, PatternState::Grey]
+            }
         }
     }
 }
 
-// application config
+/// Whether to colorize terminal output with ANSI escapes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum ColorMode {
+    Auto,
+    Always,
+    Never,
+}
+
+impl ColorMode {
+    /// Resolves this mode to a yes/no decision, checking whether stdout is a terminal for `Auto`.
+    fn enabled(self) -> bool {
+        match self {
+            ColorMode::Always => true,
+            ColorMode::Never => false,
+            ColorMode::Auto => std::io::stdout().is_terminal(),
+        }
+    }
+}
+
+/// Finds possible Wordle solutions matching observed guess patterns.
+#[derive(Parser, Debug)]
+#[command(author, version, about)]
+#[command(group(ArgGroup::new("pattern_source").args(["pattern", "pattern_file", "query"])))]
 struct Config {
+    /// Path to the wordlist file, one word per line
+    #[arg(short, long = "wordlist", default_value = "wordlist.txt")]
     wordlist_path: String,
-    solution: String,
-    pattern: String,
+
+    /// The hidden solution word. Required unless one or more `--guess` entries are given, in which case
+    /// candidates are narrowed from the guess history alone, without a known solution
+    #[arg(short, long)]
+    solution: Option<String>,
+
+    /// A real guess paired with its observed feedback, as `WORD=PATTERN` (e.g. `crane=GYXXY`). Repeat for
+    /// each guess in the history; candidates consistent with all of them are reported
+    #[arg(long = "guess", value_name = "WORD=PATTERN")]
+    guess: Vec<String>,
+
+    /// Inline query pattern rows, separated by newlines
+    #[arg(short, long)]
+    pattern: Option<String>,
+
+    /// Path to a file containing query pattern rows, one row per line
+    #[arg(short = 'f', long)]
+    pattern_file: Option<String>,
+
+    /// A boolean query expression combining pattern rows with `&` (AND), `|` (OR), `!` (NOT) and
+    /// parentheses, e.g. `(?XXX? | ???X?) & !GGGGG`
+    #[arg(short, long)]
+    query: Option<String>,
+
+    /// Whether to colorize resolved patterns and matching guesses in the output
+    #[arg(long, value_enum, default_value = "auto")]
+    color: ColorMode,
+
+    /// Guard rail on the solution's word length: pattern expansion is O(3^length), so solutions longer
+    /// than this are rejected rather than silently taking a very long time
+    #[arg(long, default_value_t = 8)]
+    max_word_length: usize,
+}
+
+impl Config {
+    /// Resolves the query pattern string, reading it from `pattern_file` if one was given.
+    fn pattern_str(&self) -> Result<String, std::io::Error> {
+        match &self.pattern_file {
+            Some(path) => fs::read_to_string(path),
+            None => Ok(self.pattern.clone().unwrap_or_default()),
+        }
+    }
 }
 
 // two approaches:
@@ -86,24 +255,87 @@ struct Config {
 // 
 // winner: 1 (yippee)
 fn main() {
-    let config: Config = Config {
-        wordlist_path: String::from("wordlist.txt"),
-        solution: String::from("ideal"),
-        pattern: String::from(
-            r#"
-            ??*??
-            ?XXX?
-            ???X?
-            ?X?X?
-            ???X?
-            GGGGG
-        "#,
-        ),
+    let config: Config = Config::parse();
+
+    let guess_history: Vec<GuessFeedback> = match config
+        .guess
+        .iter()
+        .map(|raw| parse_guess_feedback(raw))
+        .collect::<Result<_, _>>()
+    {
+        Ok(gh) => gh,
+        Err(e) => {
+            eprintln!("Failed to parse --guess: {}.", e);
+            return;
+        }
+    };
+    if let Err(e) = validate_guess_lengths(&guess_history) {
+        eprintln!("Failed to parse --guess: {}.", e);
+        return;
+    }
+
+    // reverse mode: given real guesses and their observed feedback, but no known solution, narrow down
+    // which words could still be it. This doesn't need a solution at all, so it bypasses the
+    // solution-keyed pattern matching machinery below entirely
+    if config.solution.is_none() {
+        if guess_history.is_empty() {
+            eprintln!(
+                "Must supply --solution, or one or more --guess entries to narrow candidates without a known solution."
+            );
+            return;
+        }
+
+        let word_length: usize = guess_history[0].guess.chars().count();
+        let wordlist: Vec<String> = match load_wordlist(&config.wordlist_path, word_length) {
+            Ok(words) => words,
+            Err(e) => {
+                eprintln!("Failed to load wordlist: {}.", e);
+                return;
+            }
+        };
+
+        let candidates: Vec<String> = find_possible_solutions(&wordlist, &guess_history);
+        println!(
+            "{} possible solution(s) given the guess history:",
+            candidates.len()
+        );
+        for candidate in &candidates {
+            println!("  {}", candidate);
+        }
+        return;
+    }
+    let solution: String = config.solution.clone().expect("checked above");
+
+    let has_pattern_source: bool =
+        config.pattern.is_some() || config.pattern_file.is_some() || config.query.is_some();
+    if !has_pattern_source {
+        eprintln!("Must supply one of --pattern, --pattern-file, or --query.");
+        return;
+    }
+
+    // word length is a first-class parameter inferred from the solution's character count, not its byte
+    // length, so multibyte alphabets work; it also drives expand_query_pattern's O(3^length) blowup, so
+    // guard against solutions that would make expansion impractical
+    let word_length: usize = solution.chars().count();
+    if word_length > config.max_word_length {
+        eprintln!(
+            "Solution length {} exceeds --max-word-length {} (pattern expansion is O(3^length)); pass a higher --max-word-length if you really want this.",
+            word_length, config.max_word_length
+        );
+        return;
+    }
+
+    let pattern_str: String = match config.pattern_str() {
+        Ok(s) => s,
+        Err(e) => {
+            eprintln!("Failed to read pattern: {}.", e);
+            return;
+        }
     };
 
     // load wordlist
-    // filter to only include words with the correct length just in case, although it should always be 5
-    let wordlist: Vec<String> = match load_wordlist(&config.wordlist_path, config.solution.len()) {
+    // filter to only include words with the correct length and alphabet
+    let wordlist: Vec<String> = match load_wordlist(&config.wordlist_path, word_length) {
         Ok(words) => words,
         Err(e) => {
             eprintln!("Failed to load wordlist: {}.", e);
@@ -119,32 +351,64 @@ fn main() {
     // parse patterns of every word :)
     let mut pattern_map: HashMap<Vec<PatternState>, Vec<String>> = HashMap::new();
     for word in &wordlist {
-        let pattern: Vec<PatternState> = calculate_pattern(&word, &config.solution);
+        let pattern: Vec<PatternState> = calculate_pattern(word, &solution);
         pattern_map.entry(pattern).or_default().push(word.clone());
     }
 
+    // a boolean query expression combines pattern rows with AND/OR/NOT instead of reporting each row
+    // independently
+    if let Some(query_str) = &config.query {
+        let operation: Operation = match parse_operation(query_str, word_length) {
+            Ok(op) => op,
+            Err(e) => {
+                eprintln!("Failed to parse query: {}.", e);
+                return;
+            }
+        };
+
+        let all_words: HashSet<String> = wordlist.iter().cloned().collect();
+        let mut matches: Vec<String> = evaluate_operation(&operation, &pattern_map, &all_words)
+            .into_iter()
+            .collect();
+        matches.sort();
+
+        println!("{} word(s) match the query:", matches.len());
+        for word in &matches {
+            println!("  {}", word);
+        }
+        return;
+    }
+
     // get solutions for each query pattern we want
-    let query_patterns: Vec<Vec<QueryPatternState>> = parse_query_pattern(&config.pattern);
+    let query_patterns: Vec<Vec<QueryPatternState>> =
+        match parse_query_pattern(&pattern_str, word_length) {
+            Ok(patterns) => patterns,
+            Err(e) => {
+                eprintln!("Failed to parse pattern: {}.", e);
+                return;
+            }
+        };
     let mut possible: bool = true;
-    for query in query_patterns {
-        let patterns: Vec<Vec<PatternState>> = expand_query_pattern(&query);
-        let mut solutions: Vec<&String> = Vec::new();
+    let use_color: bool = config.color.enabled();
+    for query in &query_patterns {
+        let patterns: Vec<Vec<PatternState>> = expand_query_pattern(query);
+        let mut solutions: Vec<(&Vec<PatternState>, &String)> = Vec::new();
 
         // for each (generated) pattern, get all words that match
-        for pattern in patterns {
-            if let Some(matches) = pattern_map.get(&pattern) {
+        for pattern in &patterns {
+            if let Some(matches) = pattern_map.get(pattern) {
                 // words for each pattern are guaranteed to be unique
-                solutions.extend(matches);
+                solutions.extend(matches.iter().map(|word| (pattern, word)));
             }
         }
 
-        if solutions.len() > 0 {
+        if !solutions.is_empty() {
             println!(
                 "Possible solutions for pattern {}:",
-                query_pattern_to_string(&query)
+                query_pattern_to_string(query)
             );
-            if let Some(first_solution) = solutions.first() {
-                println!("  {}", first_solution);
+            if let Some((first_pattern, first_solution)) = solutions.first() {
+                println!("  {}", render_colored_row(first_pattern, first_solution, use_color));
                 if solutions.len() > 1 {
                     println!("  (and {} others)", solutions.len() - 1);
                 }
@@ -152,7 +416,7 @@ fn main() {
         } else {
             println!(
                 "No possible solutions found for pattern {}.",
-                query_pattern_to_string(&query)
+                query_pattern_to_string(query)
             );
             possible = false;
         }
@@ -161,46 +425,431 @@ fn main() {
     if !possible {
         println!("Some patterns have no possible solutions. :(");
     }
+
+    // treat the whole board as one sequence and try to reconstruct a chain of guesses that realizes it
+    // against the solution, ending in the solution itself. Unlike a single row, the board's distinct
+    // placeholder-name count isn't bounded by word_length, so it needs its own guard against the same
+    // O(3^n) binding search blowup
+    let board_name_count: usize = collect_placeholder_names(query_patterns.iter().flatten()).len();
+    if board_name_count > config.max_word_length {
+        eprintln!(
+            "Board has {} distinct named placeholders, exceeding --max-word-length {} (binding search is O(3^names)); pass a higher --max-word-length if you really want this.",
+            board_name_count, config.max_word_length
+        );
+        return;
+    }
+
+    match reconstruct_guess_grid(&query_patterns, &pattern_map, &solution) {
+        Some(transcript) => {
+            println!("\nReconstructed guess grid:");
+            for guess in &transcript {
+                println!("  {}", guess);
+            }
+        }
+        None => println!("\nNo guess grid realizes this board."),
+    }
+
+    // if real guesses were supplied alongside a known solution, also narrow candidates from that history
+    if !guess_history.is_empty() {
+        let candidates: Vec<String> = find_possible_solutions(&wordlist, &guess_history);
+        println!(
+            "\n{} possible solution(s) given the guess history:",
+            candidates.len()
+        );
+        for candidate in &candidates {
+            println!("  {}", candidate);
+        }
+    }
 }
 
-/// Loads a wordlist from a file at the given path, returning a vector of word strings. Results are filtered to only
-/// include those that are composed of only ASCII alphabetic characters and have the specified length.
+/// Loads a wordlist from a file at the given path, returning a vector of word strings. Results are filtered
+/// to only include those that are composed entirely of alphabetic characters (any Unicode alphabet, not
+/// just ASCII) and have the specified length in characters, not bytes.
 /// If the file does not exist, or there is an error reading the file, an error is returned.
 fn load_wordlist(path: &str, word_length: usize) -> Result<Vec<String>, std::io::Error> {
     let content: String = fs::read_to_string(path)?;
     let words: Vec<String> = content
         .lines()
         .map(|s| s.trim().to_lowercase())
-        .filter(|s| s.len() == word_length && s.chars().all(|c| c.is_ascii_alphabetic()))
+        .filter(|s| s.chars().count() == word_length && s.chars().all(char::is_alphabetic))
         .collect();
     Ok(words)
 }
 
-/// Parses a query pattern string into a vector of vectors of QueryPatternState enums.
-/// Expected format:
+/// Parses a single query pattern row (e.g. `?XXX?`) into a vector of QueryPatternState enums, validating its
+/// length against `expected_len` (the solution length). `row_no` is only used to annotate errors.
+fn parse_pattern_row(
+    row: &str,
+    row_no: usize,
+    expected_len: usize,
+) -> Result<Vec<QueryPatternState>, ParseError> {
+    let mut parsed: Vec<QueryPatternState> = Vec::new();
+    let mut chars = row.char_indices().peekable();
+
+    while let Some(&(col, c)) = chars.peek() {
+        if c == '$' {
+            let dollar_col = col;
+            chars.next();
+
+            // `${name}` allows a multi-character name; bare `$x` takes exactly the one character after
+            // the `$` as the name, so placeholders can sit directly next to G/Y/X/?/* without a separator
+            let name: String = if matches!(chars.peek(), Some(&(_, '{'))) {
+                chars.next();
+                let mut name = String::new();
+                loop {
+                    match chars.next() {
+                        Some((_, '}')) => break,
+                        Some((_, c2)) => name.push(c2),
+                        None => {
+                            return Err(ParseError::UnterminatedPlaceholder {
+                                line: row_no,
+                                col: dollar_col + 1,
+                            })
+                        }
+                    }
+                }
+                name
+            } else {
+                match chars.next() {
+                    Some((_, c2)) => c2.to_string(),
+                    None => {
+                        return Err(ParseError::InvalidPatternChar {
+                            ch: '$',
+                            line: row_no,
+                            col: dollar_col + 1,
+                        })
+                    }
+                }
+            };
+
+            if name.is_empty() {
+                return Err(ParseError::InvalidPatternChar {
+                    ch: '$',
+                    line: row_no,
+                    col: dollar_col + 1,
+                });
+            }
+            parsed.push(QueryPatternState::Named(name));
+        } else {
+            chars.next();
+            let state = QueryPatternState::try_from(c).map_err(|_| ParseError::InvalidPatternChar {
+                ch: c,
+                line: row_no,
+                col: col + 1,
+            })?;
+            parsed.push(state);
+        }
+    }
+
+    if parsed.len() != expected_len {
+        return Err(ParseError::LengthMismatch {
+            expected: expected_len,
+            got: parsed.len(),
+            line: row_no,
+        });
+    }
+
+    Ok(parsed)
+}
+
+/// Parses a query pattern string into a vector of vectors of QueryPatternState enums, validating every row
+/// against `expected_len` (the solution length). Expected format:
 ///   XXXXX
 ///   YYYYY
 ///   GGGGG
 ///   ?????
 ///   *****
-fn parse_query_pattern(pattern_str: &str) -> Vec<Vec<QueryPatternState>> {
+fn parse_query_pattern(
+    pattern_str: &str,
+    expected_len: usize,
+) -> Result<Vec<Vec<QueryPatternState>>, ParseError> {
     pattern_str
         .lines()
         .map(|line| line.trim()) // trim whitespace from each line
         .filter(|line| !line.is_empty()) // filter out now-empty lines
-        .map(|line| line.chars().map(QueryPatternState::from).collect())
+        .enumerate()
+        .map(|(line_idx, line)| parse_pattern_row(line, line_idx + 1, expected_len))
         .collect()
 }
 
+/// A boolean composition of pattern rows. Mirrors a small query tree: `Leaf` holds a single pattern row
+/// (the same syntax accepted by [`parse_query_pattern`]), and `And`/`Or`/`Not` combine the word sets that
+/// their operands resolve to via [`evaluate_operation`].
+#[derive(Debug, Clone)]
+enum Operation {
+    And(Vec<Operation>),
+    Or(Vec<Operation>),
+    Not(Box<Operation>),
+    Leaf(Vec<QueryPatternState>),
+}
+
+/// A token in a boolean query expression.
+#[derive(Debug, Clone)]
+enum QueryToken {
+    LParen,
+    RParen,
+    And,
+    Or,
+    Not,
+    Pattern(String),
+}
+
+/// Splits a boolean query expression into tokens: `(`, `)`, `&`, `|`, `!`, and contiguous runs of pattern
+/// characters (treated as a single row each).
+fn tokenize_operation(input: &str) -> Result<Vec<QueryToken>, ParseError> {
+    let mut tokens: Vec<QueryToken> = Vec::new();
+    let mut chars = input.char_indices().peekable();
+
+    while let Some(&(pos, c)) = chars.peek() {
+        match c {
+            c if c.is_whitespace() => {
+                chars.next();
+            }
+            '(' => {
+                tokens.push(QueryToken::LParen);
+                chars.next();
+            }
+            ')' => {
+                tokens.push(QueryToken::RParen);
+                chars.next();
+            }
+            '&' => {
+                tokens.push(QueryToken::And);
+                chars.next();
+            }
+            '|' => {
+                tokens.push(QueryToken::Or);
+                chars.next();
+            }
+            '!' => {
+                tokens.push(QueryToken::Not);
+                chars.next();
+            }
+            _ => {
+                let mut row = String::new();
+                while let Some(&(_, c)) = chars.peek() {
+                    if "()&|!".contains(c) || c.is_whitespace() {
+                        break;
+                    }
+                    row.push(c);
+                    chars.next();
+                }
+                if row.is_empty() {
+                    return Err(ParseError::UnexpectedToken {
+                        found: c.to_string(),
+                        pos,
+                    });
+                }
+                tokens.push(QueryToken::Pattern(row));
+            }
+        }
+    }
+
+    Ok(tokens)
+}
+
+/// Recursive-descent parser for boolean query expressions, built on top of [`tokenize_operation`].
+/// Precedence (loosest to tightest): `|`, `&`, `!`.
+struct OperationParser<'a> {
+    tokens: &'a [QueryToken],
+    pos: usize,
+    expected_len: usize,
+    next_row_no: usize,
+}
+
+impl<'a> OperationParser<'a> {
+    fn parse(tokens: &'a [QueryToken], expected_len: usize) -> Result<Operation, ParseError> {
+        if tokens.is_empty() {
+            return Err(ParseError::EmptyExpression);
+        }
+
+        let mut parser = OperationParser {
+            tokens,
+            pos: 0,
+            expected_len,
+            next_row_no: 1,
+        };
+        let op = parser.parse_or()?;
+        if parser.pos != parser.tokens.len() {
+            if matches!(parser.peek(), Some(QueryToken::RParen)) {
+                return Err(ParseError::UnmatchedParen { pos: parser.pos });
+            }
+            return Err(ParseError::TrailingInput { pos: parser.pos });
+        }
+        Ok(op)
+    }
+
+    fn peek(&self) -> Option<&QueryToken> {
+        self.tokens.get(self.pos)
+    }
+
+    fn parse_or(&mut self) -> Result<Operation, ParseError> {
+        let mut operands = vec![self.parse_and()?];
+        while matches!(self.peek(), Some(QueryToken::Or)) {
+            self.pos += 1;
+            operands.push(self.parse_and()?);
+        }
+        Ok(if operands.len() == 1 {
+            operands.remove(0)
+        } else {
+            Operation::Or(operands)
+        })
+    }
+
+    fn parse_and(&mut self) -> Result<Operation, ParseError> {
+        let mut operands = vec![self.parse_not()?];
+        while matches!(self.peek(), Some(QueryToken::And)) {
+            self.pos += 1;
+            operands.push(self.parse_not()?);
+        }
+        Ok(if operands.len() == 1 {
+            operands.remove(0)
+        } else {
+            Operation::And(operands)
+        })
+    }
+
+    fn parse_not(&mut self) -> Result<Operation, ParseError> {
+        if matches!(self.peek(), Some(QueryToken::Not)) {
+            self.pos += 1;
+            return Ok(Operation::Not(Box::new(self.parse_not()?)));
+        }
+        self.parse_atom()
+    }
+
+    fn parse_atom(&mut self) -> Result<Operation, ParseError> {
+        match self.tokens.get(self.pos) {
+            Some(QueryToken::LParen) => {
+                self.pos += 1;
+                let inner = self.parse_or()?;
+                match self.tokens.get(self.pos) {
+                    Some(QueryToken::RParen) => {
+                        self.pos += 1;
+                        Ok(inner)
+                    }
+                    _ => Err(ParseError::UnmatchedParen { pos: self.pos }),
+                }
+            }
+            Some(QueryToken::Pattern(row)) => {
+                let row_no = self.next_row_no;
+                self.next_row_no += 1;
+                let parsed = parse_pattern_row(row, row_no, self.expected_len)?;
+                self.pos += 1;
+                Ok(Operation::Leaf(parsed))
+            }
+            Some(other) => Err(ParseError::UnexpectedToken {
+                found: format!("{:?}", other),
+                pos: self.pos,
+            }),
+            None => Err(ParseError::EmptyExpression),
+        }
+    }
+}
+
+/// Parses a boolean query expression (e.g. `(?XXX? | ???X?) & !GGGGG`) into an [`Operation`] tree.
+fn parse_operation(input: &str, expected_len: usize) -> Result<Operation, ParseError> {
+    let tokens = tokenize_operation(input)?;
+    OperationParser::parse(&tokens, expected_len)
+}
+
+/// Evaluates an [`Operation`] tree against `pattern_map`, returning the set of words it resolves to. A
+/// `Leaf` expands via [`expand_query_pattern`] and unions the words of every concrete pattern it matches;
+/// `Or` unions its operands' word sets, `And` intersects them, and `Not` takes the complement against
+/// `all_words`.
+fn evaluate_operation(
+    op: &Operation,
+    pattern_map: &HashMap<Vec<PatternState>, Vec<String>>,
+    all_words: &HashSet<String>,
+) -> HashSet<String> {
+    match op {
+        Operation::Leaf(row) => {
+            let mut matches: HashSet<String> = HashSet::new();
+            for pattern in expand_query_pattern(row) {
+                if let Some(words) = pattern_map.get(&pattern) {
+                    matches.extend(words.iter().cloned());
+                }
+            }
+            matches
+        }
+        Operation::And(children) => {
+            let mut children = children.iter();
+            let first = match children.next() {
+                Some(child) => evaluate_operation(child, pattern_map, all_words),
+                None => return HashSet::new(),
+            };
+            children.fold(first, |acc, child| {
+                let next = evaluate_operation(child, pattern_map, all_words);
+                acc.intersection(&next).cloned().collect()
+            })
+        }
+        Operation::Or(children) => children.iter().fold(HashSet::new(), |mut acc, child| {
+            acc.extend(evaluate_operation(child, pattern_map, all_words));
+            acc
+        }),
+        Operation::Not(inner) => {
+            let matches = evaluate_operation(inner, pattern_map, all_words);
+            all_words.difference(&matches).cloned().collect()
+        }
+    }
+}
+
 /// Expands a query pattern into a vector of vectors of PatternState enums, representing all possible patterns
 /// that match the query pattern.
-fn expand_query_pattern(pattern: &Vec<QueryPatternState>) -> Vec<Vec<PatternState>> {
+fn expand_query_pattern(pattern: &[QueryPatternState]) -> Vec<Vec<PatternState>> {
+    let names: Vec<String> = collect_placeholder_names(pattern.iter());
+    named_bindings(&names)
+        .iter()
+        .flat_map(|binding| expand_query_pattern_with_bindings(pattern, binding))
+        .collect()
+}
+
+/// Collects the distinct `Named` placeholder names appearing in `states`, in first-seen order.
+fn collect_placeholder_names<'a>(states: impl Iterator<Item = &'a QueryPatternState>) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    for state in states {
+        if let QueryPatternState::Named(name) = state {
+            if !names.contains(name) {
+                names.push(name.clone());
+            }
+        }
+    }
+    names
+}
+
+/// Enumerates every binding of `names` to a single `PatternState` each (the green/yellow/grey pool shared by
+/// `Any` and `Named`), used to tie together all occurrences of the same placeholder name.
+fn named_bindings(names: &[String]) -> Vec<HashMap<String, PatternState>> {
+    let mut bindings: Vec<HashMap<String, PatternState>> = vec![HashMap::new()];
+    for name in names {
+        let mut next_bindings: Vec<HashMap<String, PatternState>> =
+            Vec::with_capacity(bindings.len() * 3);
+        for binding in &bindings {
+            for state in [PatternState::Green, PatternState::Yellow, PatternState::Grey] {
+                let mut next_binding = binding.clone();
+                next_binding.insert(name.clone(), state);
+                next_bindings.push(next_binding);
+            }
+        }
+        bindings = next_bindings;
+    }
+    bindings
+}
+
+/// Expands a single query pattern row into all matching concrete patterns, given a fixed binding for any
+/// named placeholders it references. Positions that aren't `Named` still fan out independently as usual.
+fn expand_query_pattern_with_bindings(
+    pattern: &[QueryPatternState],
+    bindings: &HashMap<String, PatternState>,
+) -> Vec<Vec<PatternState>> {
     let mut results: Vec<Vec<PatternState>> = vec![Vec::new()];
 
-    // for each query state (G, Y, X, ?, *) in query pattern...
+    // for each query state (G, Y, X, ?, *, $name) in query pattern...
     for query_state in pattern {
-        let possible_states: Vec<PatternState> = query_state.possible_states();
-        
+        let possible_states: Vec<PatternState> = match query_state {
+            QueryPatternState::Named(name) => vec![bindings[name]],
+            other => other.possible_states(),
+        };
+
         // ...extend results with each possible state
         let mut new_results: Vec<Vec<PatternState>> = Vec::with_capacity(results.len() * possible_states.len());
         for result in &results {
@@ -216,9 +865,213 @@ fn expand_query_pattern(pattern: &Vec<QueryPatternState>) -> Vec<Vec<PatternStat
     results
 }
 
+/// Attempts to reconstruct a full guess grid: one concrete guess word per row of `rows` such that the
+/// entire board is simultaneously satisfiable against `solution`, with the final row guaranteed to be the
+/// solution itself. Returns the transcript of guesses a player could type to produce exactly that colored
+/// grid, or `None` if any row has no candidates or the final row can't be the solution.
+fn reconstruct_guess_grid(
+    rows: &[Vec<QueryPatternState>],
+    pattern_map: &HashMap<Vec<PatternState>, Vec<String>>,
+    solution: &str,
+) -> Option<Vec<String>> {
+    // named placeholders can tie positions across rows, so every row must be expanded under the same
+    // binding; try each possible binding of the board's placeholder names until one realizes the board
+    let names: Vec<String> = collect_placeholder_names(rows.iter().flatten());
+    named_bindings(&names)
+        .iter()
+        .find_map(|binding| reconstruct_guess_grid_with_bindings(rows, binding, pattern_map, solution))
+}
+
+/// Attempts to realize `rows` under one fixed binding of named placeholders; see
+/// [`reconstruct_guess_grid`].
+fn reconstruct_guess_grid_with_bindings(
+    rows: &[Vec<QueryPatternState>],
+    bindings: &HashMap<String, PatternState>,
+    pattern_map: &HashMap<Vec<PatternState>, Vec<String>>,
+    solution: &str,
+) -> Option<Vec<String>> {
+    let mut transcript: Vec<String> = Vec::with_capacity(rows.len());
+
+    for (i, row) in rows.iter().enumerate() {
+        let is_last_row = i == rows.len() - 1;
+        let candidates: Vec<&String> = expand_query_pattern_with_bindings(row, bindings)
+            .iter()
+            .filter_map(|pattern| pattern_map.get(pattern))
+            .flatten()
+            .collect();
+
+        if candidates.is_empty() {
+            return None;
+        }
+
+        if is_last_row {
+            if !candidates.iter().any(|candidate| candidate.as_str() == solution) {
+                return None;
+            }
+            transcript.push(solution.to_string());
+        } else {
+            transcript.push(candidates[0].clone());
+        }
+    }
+
+    Some(transcript)
+}
+
+/// One guess paired with its observed feedback, used as an input row to [`find_possible_solutions`].
+struct GuessFeedback {
+    guess: String,
+    pattern: Vec<PatternState>,
+}
+
+/// Parses a single `--guess` entry of the form `WORD=PATTERN` (e.g. `crane=GYXXY`) into a [`GuessFeedback`],
+/// validating that the pattern is made of G/Y/X characters and has the same length as the guess.
+fn parse_guess_feedback(raw: &str) -> Result<GuessFeedback, ParseError> {
+    let invalid = || ParseError::InvalidGuessFeedback { raw: raw.to_string() };
+
+    let (word, pattern_str) = raw.split_once('=').ok_or_else(invalid)?;
+    let pattern: Vec<PatternState> = pattern_str
+        .chars()
+        .map(PatternState::try_from)
+        .collect::<Result<_, _>>()
+        .map_err(|_| invalid())?;
+
+    if word.is_empty() || pattern.len() != word.chars().count() {
+        return Err(invalid());
+    }
+
+    Ok(GuessFeedback {
+        guess: word.to_lowercase(),
+        pattern,
+    })
+}
+
+/// Checks that every guess in `guesses` has the same length as the first one. `find_possible_solutions`
+/// sizes its per-position state off the first guess, so a mismatched later guess would otherwise index
+/// out of bounds.
+fn validate_guess_lengths(guesses: &[GuessFeedback]) -> Result<(), ParseError> {
+    let expected = match guesses.first() {
+        Some(g) => g.guess.chars().count(),
+        None => return Ok(()),
+    };
+
+    for g in guesses {
+        let got = g.guess.chars().count();
+        if got != expected {
+            return Err(ParseError::MixedGuessLengths {
+                expected,
+                got,
+                guess: g.guess.clone(),
+            });
+        }
+    }
+
+    Ok(())
+}
+
+/// Finds every word in `wordlist` that is consistent with a set of observed guess/feedback pairs. This is
+/// the inverse of the forward search in `main`: instead of fixing a solution and asking which guesses match
+/// a pattern, we fix a history of guesses and their patterns and ask which words could still be the
+/// solution.
+///
+/// Rather than re-running `calculate_pattern` against every candidate, constraints are accumulated once
+/// across all rows and then checked per candidate:
+/// - a green at position `i` requires `candidate[i] == guess[i]`
+/// - a yellow or grey at position `i` requires `candidate[i] != guess[i]`
+/// - per letter, the green+yellow occurrences in a guess give a lower bound (`min_count`) on how many times
+///   that letter appears in the solution; a grey occurrence of a letter that also appears as green/yellow in
+///   the same guess caps the count exactly at that occurrence count (duplicate-letter exhaustion), while a
+///   grey occurrence with no accompanying green/yellow caps the count at zero
+fn find_possible_solutions(wordlist: &[String], guesses: &[GuessFeedback]) -> Vec<String> {
+    let word_len = match guesses.first() {
+        Some(g) => g.guess.chars().count(),
+        None => return wordlist.to_vec(),
+    };
+
+    // position -> letter the candidate must match (from a green)
+    let mut required: Vec<Option<char>> = vec![None; word_len];
+    // position -> letters the candidate must not match (from a yellow or grey)
+    let mut forbidden_at: Vec<Vec<char>> = vec![Vec::new(); word_len];
+
+    // per-letter occurrence bounds, intersected across all guesses
+    let mut min_count: HashMap<char, usize> = HashMap::new();
+    let mut max_count: HashMap<char, usize> = HashMap::new();
+
+    for GuessFeedback { guess, pattern } in guesses {
+        let guess_chars: Vec<char> = guess.chars().collect();
+
+        // count green+yellow occurrences of each letter within this guess
+        let mut marked: HashMap<char, usize> = HashMap::new();
+        for (i, state) in pattern.iter().enumerate() {
+            if *state != PatternState::Grey {
+                *marked.entry(guess_chars[i]).or_default() += 1;
+            }
+        }
+
+        for (i, state) in pattern.iter().enumerate() {
+            let c = guess_chars[i];
+            match state {
+                PatternState::Green => required[i] = Some(c),
+                PatternState::Yellow | PatternState::Grey => forbidden_at[i].push(c),
+            }
+        }
+
+        for (&c, &count) in &marked {
+            min_count
+                .entry(c)
+                .and_modify(|existing| *existing = (*existing).max(count))
+                .or_insert(count);
+        }
+
+        // a grey for letter c caps its count at however many green/yellow occurrences of c this guess
+        // already accounted for (zero, if none)
+        for (i, state) in pattern.iter().enumerate() {
+            if *state == PatternState::Grey {
+                let cap = *marked.get(&guess_chars[i]).unwrap_or(&0);
+                max_count
+                    .entry(guess_chars[i])
+                    .and_modify(|existing| *existing = (*existing).min(cap))
+                    .or_insert(cap);
+            }
+        }
+    }
+
+    wordlist
+        .iter()
+        .filter(|word| {
+            let chars: Vec<char> = word.chars().collect();
+            if chars.len() != word_len {
+                return false;
+            }
+
+            for i in 0..word_len {
+                if let Some(c) = required[i] {
+                    if chars[i] != c {
+                        return false;
+                    }
+                }
+                if forbidden_at[i].contains(&chars[i]) {
+                    return false;
+                }
+            }
+
+            let mut counts: HashMap<char, usize> = HashMap::new();
+            for &c in &chars {
+                *counts.entry(c).or_default() += 1;
+            }
+            min_count
+                .iter()
+                .all(|(c, &min)| *counts.get(c).unwrap_or(&0) >= min)
+                && max_count
+                    .iter()
+                    .all(|(c, &max)| *counts.get(c).unwrap_or(&0) <= max)
+        })
+        .cloned()
+        .collect()
+}
+
 /// Calculates the pattern for a given guess and solution.
 fn calculate_pattern(guess: &str, solution: &str) -> Vec<PatternState> {
-    let word_len = solution.len();
+    let word_len = solution.chars().count();
     let mut pattern = vec![PatternState::Grey; word_len];
     let mut solution_chars: Vec<char> = solution.chars().collect();
     let mut guess_chars: Vec<char> = guess.chars().collect();
@@ -247,6 +1100,38 @@ fn calculate_pattern(guess: &str, solution: &str) -> Vec<PatternState> {
 }
 
 /// Converts a vector of QueryPatternState enums into a string representation, with one character per state.
-fn query_pattern_to_string(pattern: &Vec<QueryPatternState>) -> String {
+fn query_pattern_to_string(pattern: &[QueryPatternState]) -> String {
     pattern.iter().map(|s| s.to_string()).collect()
 }
+
+/// Returns the ANSI color code for a pattern state: green, yellow, or grey.
+fn ansi_color(state: PatternState) -> &'static str {
+    match state {
+        PatternState::Green => "\x1b[32m",
+        PatternState::Yellow => "\x1b[33m",
+        PatternState::Grey => "\x1b[90m",
+    }
+}
+
+/// Renders a resolved pattern alongside its matching guess as a line resembling a real Wordle row: colored
+/// block glyphs for the pattern, followed by the guess with each letter tinted to match its `PatternState`.
+/// Falls back to the plain `GYX` text if `color` is `false`.
+fn render_colored_row(pattern: &[PatternState], guess: &str, color: bool) -> String {
+    if !color {
+        let blocks: String = pattern.iter().map(|s| s.to_string()).collect();
+        return format!("{} {}", blocks, guess);
+    }
+
+    const RESET: &str = "\x1b[0m";
+    let blocks: String = pattern
+        .iter()
+        .map(|state| format!("{}\u{25a0}{}", ansi_color(*state), RESET))
+        .collect();
+    let letters: String = pattern
+        .iter()
+        .zip(guess.chars())
+        .map(|(state, c)| format!("{}{}{}", ansi_color(*state), c.to_ascii_uppercase(), RESET))
+        .collect();
+
+    format!("{} {}", blocks, letters)
+}